@@ -1,12 +1,24 @@
 //! Furi Thread API.
+//!
+//! In addition to [`spawn`]ing `'static` threads, this module also provides [`scope`] for
+//! spawning scoped threads that may borrow data from outside the scope, the
+//! [`thread_local!`] macro for per-thread storage, and [`park`]/[`Thread::unpark`] for
+//! blocking a thread without spinning.
 
-use core::time;
 #[cfg(feature = "alloc")]
 use core::{
+    any::Any,
     ffi::{c_void, CStr},
     fmt,
+    marker::PhantomData,
     ptr::NonNull,
     str,
+    sync::atomic::AtomicUsize,
+};
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time,
 };
 
 #[cfg(feature = "alloc")]
@@ -15,6 +27,7 @@ use alloc::{
     ffi::{CString, NulError},
     string::String,
     sync::Arc,
+    vec::Vec,
 };
 
 use flipperzero_sys::{self as sys, FuriFlagNoClear, FuriFlagWaitAll, FuriFlagWaitAny, HasFlag};
@@ -93,13 +106,40 @@ impl Builder {
         #[allow(clippy::arc_with_non_send_sync)] // TODO: is using `Arc` neccessary/sound here?
         let thread = Arc::new(Thread::new(name, stack_size, heap_trace_enabled));
 
-        // We need to box twice because trait objects are fat pointers, so we need the
-        // second box to obtain a thin pointer to use as the context.
-        type ThreadBody = Box<dyn FnOnce() -> i32>;
-        let thread_body: Box<ThreadBody> = Box::new(Box::new(f));
+        // Bundled with the body so that `run_thread_body` can record that the body ran
+        // to completion, for `JoinHandle::join` to report.
+        struct ThreadBody {
+            f: Box<dyn FnOnce() -> i32>,
+            completed: *const AtomicBool,
+            id: ThreadId,
+        }
+        let thread_body = Box::new(ThreadBody {
+            f: Box::new(f),
+            completed: &thread.completed,
+            id: thread.id(),
+        });
         unsafe extern "C" fn run_thread_body(context: *mut c_void) -> i32 {
-            let thread_body = unsafe { Box::from_raw(context as *mut ThreadBody) };
-            thread_body()
+            let ThreadBody { f, completed, id } =
+                *unsafe { Box::from_raw(context as *mut ThreadBody) };
+
+            // Seed the thread-ID cache with this thread's own persistent `ThreadId`
+            // *before* running its body, so that `ThreadId::current()` - including the
+            // calls `thread_local!` makes through `LocalKey::try_with` - agrees with
+            // `Thread::id()` for the rest of this thread's life. Without this, the first
+            // `ThreadId::current()` call from inside the body would mint a brand-new id
+            // that differs from `Thread::id()`, and `destroy_thread_locals` (which tears down under
+            // `Thread::id()`) would never find the entries the body registered.
+            THREAD_ID_CACHE.insert(unsafe { sys::furi_thread_get_current_id() }, id);
+
+            let result = f();
+
+            // SAFETY: `completed` points at the `AtomicBool` inside the `Thread` this
+            // body was spawned from, which is kept alive (by the `Arc` clone handed to
+            // `run_state_callback`, which is only released once the thread has stopped)
+            // until well after this call returns.
+            unsafe { &*completed }.store(true, Ordering::Release);
+
+            result
         }
         let callback: sys::FuriThreadCallback = Some(run_thread_body);
         let context = Box::into_raw(thread_body);
@@ -116,6 +156,25 @@ impl Builder {
                 // - `FuriThreadStateStopped` is always the final state.
                 let context = unsafe { Arc::from_raw(context as *mut Thread) };
 
+                // Use the thread's own persistent `ThreadId` (rather than re-deriving
+                // one from `_thread`), since the underlying Furi identifier may already
+                // be gone by the time this callback runs.
+                //
+                // SAFETY (Send): `destroy_thread_locals` drops each of this thread's
+                // `thread_local!` values (`Box<dyn Any>`, i.e. the user's `T`) in place,
+                // without moving them to another thread first. This is only sound because
+                // the firmware invokes `FuriThreadStateCallback` synchronously from
+                // `run_thread_body`'s own call stack, immediately before that thread
+                // deletes itself - never from a separate housekeeping/service thread. If
+                // that ever changed, a non-`Send` `T` (e.g. `Cell<_>`, `Rc<_>`) could be
+                // dropped on the wrong thread.
+                destroy_thread_locals(context.id());
+
+                // Free up this thread's slot in `THREAD_ID_CACHE` (if it ended up
+                // taking one - see the seeding comment above) now that it's the final
+                // state and nothing will look this thread's id up again.
+                THREAD_ID_CACHE.remove(context.id());
+
                 if let Some(thread) = Arc::into_inner(context) {
                     // SAFETY: No `Thread` instances exist at this point:
                     // - `JoinHandle` isn't Clone, and the one inside `JoinHandle` has
@@ -166,7 +225,483 @@ where
     Builder::new().spawn(f)
 }
 
+/// Notification flag reserved for waking up the owner of a [`Scope`] when one of its
+/// scoped threads finishes. Application code must not use this flag directly.
+///
+/// Bit 31 can't be used here: it is the `FuriFlagError` bit, which Furi rejects as a flag.
+#[cfg(feature = "alloc")]
+const SCOPE_NOTIFY_FLAG: u32 = 1 << 29;
+
+/// Creates a scope for spawning scoped threads.
+///
+/// Unlike [`spawn`], threads spawned via [`Scope::spawn`] can borrow non-`'static` data,
+/// as `scope` doesn't return until every spawned thread's closure has finished.
+///
+/// The threads are not joined: a scoped thread may still be finishing its own teardown
+/// (such as running its [`thread_local!`] destructors) when `scope` returns.
+///
+/// The function passed to `scope` takes a [`&Scope`](Scope) argument which can be used to
+/// spawn scoped threads via [`Scope::spawn`].
+///
+/// # Examples
+///
+/// ```
+/// let mut data = [1, 2, 3];
+///
+/// flipperzero::furi::thread::scope(|s| {
+///     s.spawn(|| {
+///         data[0] += 1;
+///         0
+///     });
+/// });
+///
+/// assert_eq!(data, [2, 2, 3]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        data: Arc::new(ScopeData {
+            running_threads: AtomicUsize::new(0),
+            owner: RawThreadId(unsafe { sys::furi_thread_get_current_id() }),
+        }),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+
+    let result = f(&scope);
+
+    // Wait for every thread spawned through `scope` to finish. `SCOPE_NOTIFY_FLAG` is
+    // latched by `furi_thread_flags_set`, so a worker that finishes (and notifies us)
+    // before we start waiting isn't lost: the flag is still set when we get here.
+    while scope.data.running_threads.load(Ordering::Acquire) != 0 {
+        let _ = wait_any_flags(SCOPE_NOTIFY_FLAG, true, FuriDuration::MAX);
+    }
+
+    result
+}
+
+/// A scope for spawning scoped threads.
+///
+/// See [`scope`] for details.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Scope<'scope, 'env: 'scope> {
+    data: Arc<ScopeData>,
+    /// Invariant over `'scope`, to make sure that `'scope` is exactly the minimum
+    /// lifetime requested by the user.
+    scope: PhantomData<&'scope mut &'scope ()>,
+    /// Invariant over `'env`, for the same reason as above.
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+#[cfg(feature = "alloc")]
+struct ScopeData {
+    running_threads: AtomicUsize,
+    owner: RawThreadId,
+}
+
+#[cfg(feature = "alloc")]
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawns a new thread with [`Builder`]'s default settings, and returns a
+    /// [`ScopedJoinHandle`] for it.
+    ///
+    /// Unlike [`self::spawn`], [`scope`] doesn't return until this thread's closure has
+    /// finished, so it's possible for the spawned thread to borrow data owned outside the
+    /// scope (with lifetime `'env`), as long as that data outlives the scope.
+    ///
+    /// The join handle provides a [`join`](ScopedJoinHandle::join) method that can be used
+    /// to join the spawned thread explicitly; otherwise, [`scope`] only waits for its
+    /// closure to return and does not join the thread itself.
+    pub fn spawn<F>(&'scope self, f: F) -> ScopedJoinHandle<'scope>
+    where
+        F: FnOnce() -> i32,
+        F: Send + 'scope,
+    {
+        self.data.running_threads.fetch_add(1, Ordering::Relaxed);
+
+        let data = self.data.clone();
+        let body = move || {
+            let result = f();
+            data.running_threads.fetch_sub(1, Ordering::Release);
+            let _ = set_flags(data.owner, SCOPE_NOTIFY_FLAG);
+            result
+        };
+
+        // SAFETY: `Box<dyn FnOnce() -> i32 + Send + 'scope>` doesn't actually live for
+        // `'static`, but `scope` (the only way to construct a `Scope`) doesn't return
+        // until every thread spawned through it - including this one - has finished, so
+        // the closure (and anything with lifetime `'scope` or `'env` that it captures)
+        // cannot be used after its borrows expire.
+        let body: Box<dyn FnOnce() -> i32 + Send + 'scope> = Box::new(body);
+        let body: Box<dyn FnOnce() -> i32 + Send + 'static> = unsafe { core::mem::transmute(body) };
+
+        ScopedJoinHandle {
+            inner: Builder::new().spawn(body),
+            scope: PhantomData,
+        }
+    }
+}
+
+/// An owned permission to join on a scoped thread (block on its termination).
+///
+/// See [`Scope::spawn`] for details.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct ScopedJoinHandle<'scope> {
+    inner: JoinHandle,
+    scope: PhantomData<&'scope ()>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'scope> ScopedJoinHandle<'scope> {
+    /// Extracts a handle to the underlying thread.
+    pub fn thread(&self) -> &Thread {
+        self.inner.thread()
+    }
+
+    /// Waits for the associated thread to finish.
+    ///
+    /// This function will return immediately if the associated thread has already
+    /// finished.
+    ///
+    /// See [`JoinHandle::join`] for the meaning of the returned [`Result`].
+    pub fn join(self) -> Result<i32, JoinError> {
+        self.inner.join()
+    }
+
+    /// Checks if the associated thread has finished running its main function.
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ScopedJoinHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedJoinHandle").finish_non_exhaustive()
+    }
+}
+
+/// A minimal spinlock, used to guard [`THREAD_LOCAL_REGISTRY`] and [`THREAD_ID_CACHE`]
+/// without pulling in a full `furi::sync::Mutex` for what is expected to be an
+/// uncontended, short critical section.
+struct SpinLock(AtomicBool);
+
+impl SpinLock {
+    const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    fn lock(&self) {
+        while self.0.swap(true, Ordering::Acquire) {
+            yield_now();
+        }
+    }
+
+    fn unlock(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// The per-thread values registered against a [`LocalKey`], keyed by the key's own
+/// `'static` address.
+#[cfg(feature = "alloc")]
+struct ThreadLocalMap {
+    /// Set while this thread's destructors are running, so that [`LocalKey::try_with`]
+    /// can refuse to lazily initialize new values instead of leaking them.
+    destroying: bool,
+    slots: Vec<(usize, Box<dyn Any>)>,
+}
+
+/// Registry of [`ThreadLocalMap`]s, one per thread that has touched a [`LocalKey`].
+///
+/// There's no `furi_thread`-level thread-local-storage slot exposed to Rust, so instead
+/// each thread's keys are tracked here, indexed by [`ThreadId`].
+#[cfg(feature = "alloc")]
+struct ThreadLocalRegistry {
+    lock: SpinLock,
+    threads: UnsafeCell<Vec<(ThreadId, Box<ThreadLocalMap>)>>,
+}
+
+// SAFETY: All access to `threads` is guarded by `lock`.
+#[cfg(feature = "alloc")]
+unsafe impl Sync for ThreadLocalRegistry {}
+
+#[cfg(feature = "alloc")]
+impl ThreadLocalRegistry {
+    fn with_threads<R>(&self, f: impl FnOnce(&mut Vec<(ThreadId, Box<ThreadLocalMap>)>) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.threads.get() });
+        self.lock.unlock();
+        result
+    }
+}
+
+#[cfg(feature = "alloc")]
+static THREAD_LOCAL_REGISTRY: ThreadLocalRegistry = ThreadLocalRegistry {
+    lock: SpinLock::new(),
+    threads: UnsafeCell::new(Vec::new()),
+};
+
+/// An error returned by [`LocalKey::try_with`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessError {
+    _private: (),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("already destroyed")
+    }
+}
+
+/// A thread-local storage key which owns its contents.
+///
+/// Values are kept in a lock-guarded global registry (searched linearly by thread and
+/// key), lazily initializing the value for each thread the first time it's accessed. Each
+/// thread has its own, independently initialized copy of the value; threads don't share
+/// state.
+///
+/// Use the [`thread_local!`] macro to create instances of this type, rather than
+/// constructing it directly.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct LocalKey<T: 'static> {
+    init: fn() -> T,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> LocalKey<T> {
+    #[doc(hidden)]
+    pub const fn new(init: fn() -> T) -> Self {
+        Self { init }
+    }
+
+    /// Acquires a reference to the value in this TLS key, lazily initializing it with
+    /// this key's initializer function on the first call from each thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key currently has its destructor running, and it **may** panic if
+    /// the destructor has previously been run for this thread.
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.try_with(f)
+            .expect("cannot access a Thread Local Storage value during or after destruction")
+    }
+
+    /// Acquires a reference to the value in this TLS key, lazily initializing it with
+    /// this key's initializer function on the first call from each thread.
+    ///
+    /// Unlike [`with`](Self::with), this function will not panic. Instead, it returns an
+    /// error if the key's destructor is running on the current thread.
+    pub fn try_with<F, R>(&'static self, f: F) -> Result<R, AccessError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let key_id = self as *const Self as usize;
+        let tid = ThreadId::current();
+
+        // What `with_threads` below found for this key, computed without ever handing
+        // out a `&mut ThreadLocalMap` that's still live once we call back into user code
+        // (`self.init` or `f`, below) - since that code is free to reentrantly call
+        // `with`/`try_with` again on this thread, for this same key or another one, and
+        // every key's slots on a thread live in the one `ThreadLocalMap` for that
+        // thread. Holding a `&mut` across such a call would let a reentrant call take a
+        // second, aliasing `&mut` to the same map.
+        enum Slot {
+            Existing(*const dyn Any),
+            Destroying,
+            Vacant,
+        }
+
+        let find_slot = |threads: &mut Vec<(ThreadId, Box<ThreadLocalMap>)>| {
+            let idx = match threads.iter().position(|(id, _)| *id == tid) {
+                Some(idx) => idx,
+                None => {
+                    threads.push((
+                        tid,
+                        Box::new(ThreadLocalMap {
+                            destroying: false,
+                            slots: Vec::new(),
+                        }),
+                    ));
+                    threads.len() - 1
+                }
+            };
+            let map = &mut *threads[idx].1;
+
+            if let Some(slot_idx) = map.slots.iter().position(|(id, _)| *id == key_id) {
+                Slot::Existing(&*map.slots[slot_idx].1 as *const dyn Any)
+            } else if map.destroying {
+                Slot::Destroying
+            } else {
+                Slot::Vacant
+            }
+        };
+
+        let value_ptr = match THREAD_LOCAL_REGISTRY.with_threads(find_slot) {
+            Slot::Existing(ptr) => ptr,
+            Slot::Destroying => return Err(AccessError { _private: () }),
+            Slot::Vacant => {
+                // Run the initializer with the registry's lock released and no map
+                // borrow alive, so it may freely touch other `thread_local!` keys - or,
+                // reentrantly, this one - on this thread.
+                let value: Box<dyn Any> = Box::new((self.init)());
+
+                THREAD_LOCAL_REGISTRY.with_threads(|threads| {
+                    let idx = threads
+                        .iter()
+                        .position(|(id, _)| *id == tid)
+                        .expect("this thread's map was created above and isn't removed until the thread stops");
+                    let map = &mut *threads[idx].1;
+
+                    // A reentrant `with`/`try_with` call for this same key (made from
+                    // `self.init` above) may have already inserted a slot for it; keep
+                    // that one rather than pushing a duplicate.
+                    let slot_idx = match map.slots.iter().position(|(id, _)| *id == key_id) {
+                        Some(slot_idx) => slot_idx,
+                        None => {
+                            map.slots.push((key_id, value));
+                            map.slots.len() - 1
+                        }
+                    };
+
+                    &*map.slots[slot_idx].1 as *const dyn Any
+                })
+            }
+        };
+
+        // SAFETY: `value_ptr` points into a `Box` owned by this thread's entry in
+        // `THREAD_LOCAL_REGISTRY`. Per the registry's own safety comment, only this
+        // thread ever dereferences it, and nothing removes or replaces this particular
+        // slot while this thread is still running (appending further slots to the
+        // `Vec` can reallocate it, but not the `Box` allocations it holds). The
+        // registry's lock isn't held here, so a reentrant call on this thread can't
+        // deadlock, and it can't invalidate this reference either.
+        let value = unsafe { &*value_ptr }
+            .downcast_ref::<T>()
+            .expect("a LocalKey's slot is only ever populated with its own T");
+        Ok(f(value))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> fmt::Debug for LocalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalKey").finish_non_exhaustive()
+    }
+}
+
+/// Runs and clears every thread-local destructor registered for `id`, then removes its
+/// entry from [`THREAD_LOCAL_REGISTRY`].
+///
+/// Called from [`Builder::spawn`]'s state callback once a thread has fully stopped.
+#[cfg(feature = "alloc")]
+fn destroy_thread_locals(id: ThreadId) {
+    let map_ptr = THREAD_LOCAL_REGISTRY.with_threads(|threads| {
+        threads
+            .iter()
+            .position(|(tid, _)| *tid == id)
+            .map(|idx| &mut *threads[idx].1 as *mut ThreadLocalMap)
+    });
+
+    let Some(map_ptr) = map_ptr else {
+        return;
+    };
+
+    // SAFETY: see `LocalKey::try_with` above; the only thread that could otherwise touch
+    // this entry has already stopped running, so nothing races us here.
+    let map = unsafe { &mut *map_ptr };
+    map.destroying = true;
+    while let Some((_, value)) = map.slots.pop() {
+        drop(value);
+    }
+
+    THREAD_LOCAL_REGISTRY.with_threads(|threads| {
+        if let Some(idx) = threads.iter().position(|(tid, _)| *tid == id) {
+            threads.remove(idx);
+        }
+    });
+}
+
+/// Declares a new thread-local storage key of type [`LocalKey`].
+///
+/// See [`LocalKey`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use core::cell::Cell;
+///
+/// flipperzero::thread_local! {
+///     static COUNTER: Cell<u32> = Cell::new(0);
+/// }
+///
+/// COUNTER.with(|c| c.set(c.get() + 1));
+/// assert_eq!(COUNTER.with(|c| c.get()), 1);
+/// ```
+///
+/// Each thread's values are dropped once that thread stops, even though the key itself
+/// is `'static`. Like every doctest in this crate, this only compiles against the host
+/// target (there's no Furi firmware to link against off-device) - it illustrates the
+/// intended behavior, but isn't run, so it isn't a substitute for an on-device check:
+///
+/// ```
+/// use core::sync::atomic::{AtomicBool, Ordering};
+///
+/// static DESTROYED: AtomicBool = AtomicBool::new(false);
+///
+/// struct SetOnDrop;
+///
+/// impl Drop for SetOnDrop {
+///     fn drop(&mut self) {
+///         DESTROYED.store(true, Ordering::Release);
+///     }
+/// }
+///
+/// flipperzero::thread_local! {
+///     static GUARD: SetOnDrop = SetOnDrop;
+/// }
+///
+/// let handle = flipperzero::furi::thread::spawn(|| {
+///     GUARD.with(|_| {});
+///     0
+/// });
+/// handle.join().unwrap();
+///
+/// assert!(DESTROYED.load(Ordering::Acquire));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[macro_export]
+macro_rules! thread_local {
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $init:expr; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: $crate::furi::thread::LocalKey<$t> =
+            $crate::furi::thread::LocalKey::new(|| $init);
+
+        $crate::thread_local!($($rest)*);
+    };
+}
+
 /// Gets a handle to the thread that invokes it.
+///
+/// Note that, unlike threads spawned via [`Builder`], a thread obtained this way that
+/// wasn't itself spawned via `Builder` (e.g. the application's main thread) has no
+/// `FuriThreadStateStopped` callback to run its [`thread_local!`] destructors from, so
+/// those destructors only run on a best-effort basis, if at all.
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn current() -> Thread {
@@ -190,7 +725,14 @@ pub fn current() -> Thread {
             })
     };
 
-    Thread { name, thread }
+    Thread {
+        name,
+        thread,
+        id: ThreadId::current(),
+        // Meaningless here: `current()` doesn't hand out a `JoinHandle`, so nothing
+        // ever reads this thread's `completed` flag.
+        completed: AtomicBool::new(false),
+    }
 }
 
 /// Cooperatively gives up a timeslice to the OS scheduler.
@@ -232,15 +774,134 @@ pub fn sleep_ticks(duration: FuriDuration) {
     }
 }
 
-/// A unique identifier for a running thread.
-#[derive(Copy, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct ThreadId(sys::FuriThreadId);
+/// Generates the next process-wide unique thread identifier.
+fn next_thread_id() -> ThreadId {
+    static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(0);
+
+    let mut last = NEXT_THREAD_ID.load(Ordering::Relaxed);
+    loop {
+        // Like the Rust standard library, we treat running out of `u64`s as a bug: it
+        // would require generating a new thread ID every nanosecond for over 500 years.
+        let id = last
+            .checked_add(1)
+            .expect("more than u64::MAX thread IDs allocated");
+        match NEXT_THREAD_ID.compare_exchange_weak(last, id, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return ThreadId(id),
+            Err(current) => last = current,
+        }
+    }
+}
+
+/// Number of live Furi thread identifiers that [`ThreadId::current`] and
+/// [`ThreadId::from_furi_thread`] can remember at once. Threads spawned via [`Builder`]
+/// are seeded with their own [`ThreadId`]; other threads get one on first lookup.
+const MAX_CACHED_THREAD_IDS: usize = 32;
+
+/// Caches the [`ThreadId`] of each live Furi thread (seeded for threads spawned via
+/// [`Builder`], assigned on first lookup for others), so that repeated calls to
+/// [`ThreadId::current`]/[`ThreadId::from_furi_thread`] for the same underlying thread
+/// return the same value.
+struct ThreadIdCache {
+    lock: SpinLock,
+    entries: UnsafeCell<[Option<(sys::FuriThreadId, ThreadId)>; MAX_CACHED_THREAD_IDS]>,
+}
+
+// SAFETY: All access to `entries` is guarded by `lock`.
+unsafe impl Sync for ThreadIdCache {}
+
+impl ThreadIdCache {
+    fn id_for(&self, raw: sys::FuriThreadId) -> ThreadId {
+        self.lock.lock();
+        let entries = unsafe { &mut *self.entries.get() };
+
+        let id = entries
+            .iter()
+            .find_map(|entry| entry.and_then(|(r, id)| (r == raw).then_some(id)))
+            .unwrap_or_else(|| {
+                let id = next_thread_id();
+                // If every slot is taken, the ID is still returned but not remembered: a
+                // later lookup for the same `raw` will mint (and likely cache) a
+                // different one. This only matters for a device juggling more than
+                // `MAX_CACHED_THREAD_IDS` cached threads (`Builder`-spawned or not) at once.
+                if let Some(slot) = entries.iter_mut().find(|entry| entry.is_none()) {
+                    *slot = Some((raw, id));
+                }
+                id
+            });
+
+        self.lock.unlock();
+        id
+    }
+
+    /// Associates `raw` with `id`, overwriting any existing mapping for `raw` - and, if
+    /// every slot is taken and `raw` isn't already one of them, overwriting an arbitrary
+    /// slot rather than dropping the new mapping.
+    ///
+    /// Used to seed the cache with a thread's own persistent [`ThreadId`] (minted once,
+    /// in [`Thread::new`]) before that thread runs, so that a later [`ThreadId::current`]
+    /// call from inside the thread agrees with [`Thread::id`] instead of minting a second,
+    /// different id for the same thread. Unlike [`id_for`](Self::id_for)'s best-effort
+    /// caching, this can't be allowed to silently fail: a thread whose own seed didn't
+    /// stick would never again agree with itself about its own id, breaking every
+    /// [`destroy_thread_locals`] lookup for it. [`Self::remove`] keeps this from starving
+    /// other threads' entries by reclaiming slots as threads stop.
+    fn insert(&self, raw: sys::FuriThreadId, id: ThreadId) {
+        self.lock.lock();
+        let entries = unsafe { &mut *self.entries.get() };
+
+        let slot = entries
+            .iter_mut()
+            .find(|entry| matches!(entry, Some((r, _)) if *r == raw))
+            .or_else(|| entries.iter_mut().find(|entry| entry.is_none()))
+            .or_else(|| entries.iter_mut().next())
+            .expect("MAX_CACHED_THREAD_IDS is non-zero");
+        *slot = Some((raw, id));
+
+        self.lock.unlock();
+    }
+
+    /// Clears any cache entry that maps a raw Furi id to `id`.
+    ///
+    /// Called once a thread has stopped, so that its slot doesn't sit occupied forever:
+    /// without this, `MAX_CACHED_THREAD_IDS` distinct threads that each only ran once
+    /// would be enough to permanently fill the cache, after which `id_for` would stop
+    /// remembering *any* mapping (see the "every slot taken" fallback above), and every
+    /// `insert` seed would have to fall back to evicting an arbitrary slot.
+    fn remove(&self, id: ThreadId) {
+        self.lock.lock();
+        let entries = unsafe { &mut *self.entries.get() };
+
+        if let Some(slot) = entries
+            .iter_mut()
+            .find(|entry| matches!(entry, Some((_, i)) if *i == id))
+        {
+            *slot = None;
+        }
+
+        self.lock.unlock();
+    }
+}
+
+static THREAD_ID_CACHE: ThreadIdCache = ThreadIdCache {
+    lock: SpinLock::new(),
+    entries: UnsafeCell::new([None; MAX_CACHED_THREAD_IDS]),
+};
+
+/// A unique identifier for a thread.
+///
+/// Unlike the underlying Furi thread identifier (see [`Thread::furi_id`]), a `ThreadId`
+/// remains valid, and distinguishable from every other thread's, for as long as the
+/// firmware keeps running - even after the thread it identifies has terminated. This
+/// makes it suitable as a key in long-lived maps (for example, to record per-thread
+/// state after a thread has already stopped).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadId(u64);
 
 impl ThreadId {
     /// Get the `ThreadId` for the current thread.
     pub fn current() -> Self {
-        ThreadId(unsafe { sys::furi_thread_get_current_id() })
+        let raw = unsafe { sys::furi_thread_get_current_id() };
+        THREAD_ID_CACHE.id_for(raw)
     }
 
     /// Get the `ThreadId` for a specific C `FuriThread`.
@@ -249,14 +910,40 @@ impl ThreadId {
     ///
     /// The thread pointer must be non-null and point to a valid `FuriThread`.
     pub unsafe fn from_furi_thread(thread: *mut sys::FuriThread) -> ThreadId {
-        ThreadId(unsafe { sys::furi_thread_get_id(thread) })
+        let raw = unsafe { sys::furi_thread_get_id(thread) };
+        THREAD_ID_CACHE.id_for(raw)
     }
 }
 
+/// The *raw* Furi identifier for a live thread, for interop with Flipper Zero SDK calls
+/// (such as [`set_flags`]) that need to reach the thread directly.
+///
+/// Unlike [`ThreadId`], a `RawThreadId` becomes meaningless - and may even be reused by
+/// the firmware for an unrelated thread - once the thread it was obtained from
+/// terminates. See [`Thread::furi_id`].
+#[derive(Copy, Clone)]
+pub struct RawThreadId(sys::FuriThreadId);
+
+// SAFETY: `FuriThreadId` is only ever used as an opaque identifier passed back to Furi
+// APIs that look the corresponding thread up by it - never dereferenced - so moving or
+// sharing it between threads is sound even though the underlying FFI type isn't declared
+// `Send`/`Sync`.
+unsafe impl Send for RawThreadId {}
+unsafe impl Sync for RawThreadId {}
+
 /// Set one-or-more notification flags on a thread.
 ///
 /// Returns the value of the thread's notification flags after the specified `flags` have been set.
-pub fn set_flags(thread_id: ThreadId, flags: u32) -> Result<u32, sys::furi::Status> {
+///
+/// # Breaking change
+///
+/// This used to take a [`ThreadId`]. Now that `ThreadId` is an opaque, process-wide
+/// identifier that outlives its thread (see [`ThreadId`]'s docs), it can no longer be
+/// turned back into the raw Furi identifier this function (and the underlying firmware
+/// call) actually needs - that's what [`RawThreadId`] is for. Callers passing a
+/// `ThreadId` through here should switch to [`Thread::furi_id`] (or, inside the thread
+/// itself, [`RawThreadId`] obtained some other way) instead.
+pub fn set_flags(thread_id: RawThreadId, flags: u32) -> Result<u32, sys::furi::Status> {
     let result = unsafe { sys::furi_thread_flags_set(thread_id.0, flags) };
 
     if sys::FuriFlag(result).has_flag(sys::FuriFlagError) {
@@ -326,6 +1013,37 @@ pub fn wait_all_flags(
     Ok(result)
 }
 
+/// Notification flag bit reserved by [`park`], [`park_timeout`] and [`Thread::unpark`].
+///
+/// Applications that call [`set_flags`]/[`wait_any_flags`]/[`wait_all_flags`] directly on
+/// a thread that also parks itself must avoid this bit, or the two mechanisms will steal
+/// each other's wakeups. Bit 29 is likewise reserved on a thread that calls [`scope`],
+/// which waits on it for its scoped threads to finish.
+pub const PARK_BIT: u32 = 1 << 30;
+
+/// Blocks unless or until the current thread's token is made available.
+///
+/// A call to `park` does not guarantee that the thread will remain parked forever, so
+/// callers should check the condition they're waiting for in a loop.
+///
+/// # Tokens
+///
+/// Every thread has an associated token, which starts out absent. [`park`] blocks the
+/// current thread unless or until the token is available, at which point it atomically
+/// consumes the token. [`Thread::unpark`] atomically makes the token available if it
+/// wasn't already. Because the token is latched, a call to `unpark` that happens before a
+/// call to `park` isn't lost: the subsequent `park` will see the token and return
+/// immediately.
+pub fn park() {
+    let _ = wait_any_flags(PARK_BIT, true, FuriDuration::MAX);
+}
+
+/// Like [`park`], but blocks for at most `timeout`, regardless of whether the token
+/// became available.
+pub fn park_timeout(timeout: FuriDuration) {
+    let _ = wait_any_flags(PARK_BIT, true, timeout);
+}
+
 /// A handle to a thread.
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -333,6 +1051,11 @@ pub struct Thread {
     /// Guaranteed to be UTF-8.
     name: Option<CString>,
     thread: NonNull<sys::FuriThread>,
+    id: ThreadId,
+    /// Set by `run_thread_body` once the thread's body has returned normally. Read by
+    /// [`JoinHandle::join`] to tell a normal exit apart from the thread being torn down
+    /// some other way.
+    completed: AtomicBool,
 }
 
 #[cfg(feature = "alloc")]
@@ -358,22 +1081,27 @@ impl Thread {
             Thread {
                 name,
                 thread: NonNull::new_unchecked(thread),
+                id: next_thread_id(),
+                completed: AtomicBool::new(false),
             }
         }
     }
 
     /// Gets the thread's unique identifier.
     ///
-    /// Returns `None` if the thread has terminated.
-    pub fn id(&self) -> Option<ThreadId> {
-        // TODO: The Rust stdlib generates its own unique IDs for threads that are valid
-        // even after a thread terminates.
+    /// Unlike [`furi_id`](Self::furi_id), this remains valid, and distinguishable from
+    /// every other thread's, even after the thread has terminated.
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+
+    /// Gets the thread's raw Furi identifier, for interop with the Flipper Zero SDK.
+    ///
+    /// Returns `None` if the thread has terminated, since the firmware may reuse the
+    /// underlying identifier for a subsequently spawned thread.
+    pub fn furi_id(&self) -> Option<RawThreadId> {
         let id = unsafe { sys::furi_thread_get_id(self.thread.as_ptr()) };
-        if id.is_null() {
-            None
-        } else {
-            Some(ThreadId(id))
-        }
+        (!id.is_null()).then_some(RawThreadId(id))
     }
 
     /// Gets the thread's name.
@@ -388,6 +1116,16 @@ impl Thread {
     fn cname(&self) -> Option<&CStr> {
         self.name.as_deref()
     }
+
+    /// Atomically makes this thread's token available, waking it up if it is currently
+    /// blocked in [`park`]/[`park_timeout`].
+    ///
+    /// Does nothing if the thread has already terminated.
+    pub fn unpark(&self) {
+        if let Some(id) = self.furi_id() {
+            let _ = set_flags(id, PARK_BIT);
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -410,6 +1148,22 @@ impl ufmt::uDebug for Thread {
     }
 }
 
+/// An error returned by [`JoinHandle::join`] when the associated thread did not run its
+/// body to completion.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JoinError {
+    _private: (),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("thread did not terminate normally")
+    }
+}
+
 /// An owned permission to join on a thread (block on its termination).
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -445,17 +1199,41 @@ impl JoinHandle {
     ///
     /// This function will return immediately if the associated thread has already
     /// finished.
-    pub fn join(self) -> i32 {
+    ///
+    /// Returns the thread's return code, or `Err(JoinError)` if the thread didn't run
+    /// its body to completion (for example, because it was forcibly terminated).
+    ///
+    /// # Examples
+    ///
+    /// A thread that runs its body to completion joins with `Ok` of its return code. Like
+    /// every doctest in this crate, this only compiles against the host target - there's
+    /// no Furi firmware to actually link against and run it off-device, so this
+    /// illustrates the intended behavior rather than verifying it:
+    ///
+    /// ```
+    /// let handle = flipperzero::furi::thread::spawn(|| 42);
+    /// assert_eq!(handle.join(), Ok(42));
+    /// ```
+    ///
+    /// `Err(JoinError)` is reserved for abnormal termination (the thread being torn down
+    /// some other way than returning from its body), which can't be triggered from safe
+    /// Rust, so it isn't demonstrated here.
+    pub fn join(self) -> Result<i32, JoinError> {
         let thread = self.thread();
         unsafe {
             sys::furi_thread_join(thread.thread.as_ptr());
-            sys::furi_thread_get_return_code(thread.thread.as_ptr())
+        }
+
+        if thread.completed.load(Ordering::Acquire) {
+            Ok(unsafe { sys::furi_thread_get_return_code(thread.thread.as_ptr()) })
+        } else {
+            Err(JoinError { _private: () })
         }
     }
 
     /// Checks if the associated thread has finished running its main function.
     pub fn is_finished(&self) -> bool {
-        self.thread().id().is_none()
+        self.thread().furi_id().is_none()
     }
 }
 